@@ -0,0 +1,20 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::Error,
+};
+
+/// Addition intruction set
+pub trait AddInstructions<F: FieldExt>: Chip<F> {
+    /// Numeric variable
+    type Num;
+
+    /// Addition instruction
+    /// Takes two inputs and return the sum
+    fn add(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+}
@@ -2,19 +2,18 @@ use std::marker::PhantomData;
 
 use halo2_proofs::{
     arithmetic::FieldExt,
-    circuit::{AssignedCell, Chip, Layouter, Value},
-    plonk::{Advice, Column, ConstraintSystem, Error, Instance},
+    circuit::{AssignedCell, Chip, Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Instance},
+    poly::Rotation,
 };
 
 use crate::chips::{
-    add::{AddChip, AddConfig, AddInstructions},
-    mul::{MulChip, MulConfig, MulInstructions},
-    sub::{SubChip, SubConfig, SubInstructions},
+    add::AddInstructions, div::DivInstructions, mul::MulInstructions, sub::SubInstructions,
 };
 
 /// Top-level arithmetic instruction set
 pub trait ArithmeticInstructions<F: FieldExt>:
-    AddInstructions<F> + MulInstructions<F> + SubInstructions<F>
+    AddInstructions<F> + MulInstructions<F> + SubInstructions<F> + DivInstructions<F>
 {
     /// Numeric variable
     type Num;
@@ -26,6 +25,14 @@ pub trait ArithmeticInstructions<F: FieldExt>:
         value: Value<F>,
     ) -> Result<<Self as ArithmeticInstructions<F>>::Num, Error>;
 
+    /// Loads a fixed constant into the circuit, binding the returned cell to
+    /// `constant` via the fixed column's permanent equality constraint
+    fn load_constant(
+        &self,
+        layouter: impl Layouter<F>,
+        constant: F,
+    ) -> Result<<Self as ArithmeticInstructions<F>>::Num, Error>;
+
     /// Expose a number as a public input to the circuit
     fn expose_public(
         &self,
@@ -33,6 +40,16 @@ pub trait ArithmeticInstructions<F: FieldExt>:
         num: <Self as ArithmeticInstructions<F>>::Num,
         row: usize,
     ) -> Result<(), Error>;
+
+    /// Fused instruction computing `(a + b) * c` by chaining the addition and
+    /// multiplication instructions, saving callers from wiring both regions themselves
+    fn add_and_mul(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: <Self as ArithmeticInstructions<F>>::Num,
+        b: <Self as ArithmeticInstructions<F>>::Num,
+        c: <Self as ArithmeticInstructions<F>>::Num,
+    ) -> Result<<Self as ArithmeticInstructions<F>>::Num, Error>;
 }
 
 /// Numeric variable type
@@ -44,6 +61,11 @@ pub struct Number<F: FieldExt>(pub AssignedCell<F, F>);
 
 /// Top-level arithmetic chip configuration
 /// Derived in `Chip::configure`
+///
+/// `add`, `sub` and `mul` used to each own a selector and a `create_gate`
+/// call of their own. They, and `div`, now all share this single universal
+/// PLONK gate `q_m*a*b + q_l*a + q_r*b + q_o*out + q_c = 0`: each instruction
+/// just assigns the `q_*` fixed values that realize it on its row.
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub struct ArithmeticConfig {
@@ -51,14 +73,20 @@ pub struct ArithmeticConfig {
     a: Column<Advice>,
     /// Advice column for `input_a` and `output`
     b: Column<Advice>,
+    /// Fixed column for loading constants
+    constant: Column<Fixed>,
     /// Instance column for public inputs
     instance: Column<Instance>,
-    /// Addition chip configuration
-    add_config: AddConfig,
-    /// Subtraction chip configuration
-    sub_config: SubConfig,
-    /// Multiplication chip configuration
-    mul_config: MulConfig,
+    /// Fixed selector coefficient for the `a * b` term
+    q_m: Column<Fixed>,
+    /// Fixed selector coefficient for the `a` term
+    q_l: Column<Fixed>,
+    /// Fixed selector coefficient for the `b` term
+    q_r: Column<Fixed>,
+    /// Fixed selector coefficient for the `out` term
+    q_o: Column<Fixed>,
+    /// Fixed selector coefficient for the constant term
+    q_c: Column<Fixed>,
 }
 
 /// Arithmetic chip definition
@@ -87,28 +115,84 @@ impl<F: FieldExt> ArithmeticChip<F> {
         meta: &mut ConstraintSystem<F>,
         a: Column<Advice>,
         b: Column<Advice>,
+        constant: Column<Fixed>,
         instance: Column<Instance>,
     ) -> <Self as Chip<F>>::Config {
-        // configure addition chip
-        let add_config = AddChip::configure(meta, a, b);
-        // configure substraction chip
-        let sub_config = SubChip::configure(meta, a, b);
-        // configure multiplication chip
-        let mul_config = MulChip::configure(meta, a, b);
+        // enable equality on columns
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+
+        // get the fixed selector coefficient columns
+        let q_m = meta.fixed_column();
+        let q_l = meta.fixed_column();
+        let q_r = meta.fixed_column();
+        let q_o = meta.fixed_column();
+        let q_c = meta.fixed_column();
+
+        // define the universal arithmetic gate
+        meta.create_gate(
+            "arithmetic", // gate name
+            |meta| {
+                // gate logic
+
+                // query advice value from a and b on the current rotation,
+                // and the output on the next rotation
+                let lhs = meta.query_advice(a, Rotation::cur());
+                let rhs = meta.query_advice(b, Rotation::cur());
+                let out = meta.query_advice(a, Rotation::next());
+
+                // query the fixed selector coefficients for this row
+                let q_m = meta.query_fixed(q_m, Rotation::cur());
+                let q_l = meta.query_fixed(q_l, Rotation::cur());
+                let q_r = meta.query_fixed(q_r, Rotation::cur());
+                let q_o = meta.query_fixed(q_o, Rotation::cur());
+                let q_c = meta.query_fixed(q_c, Rotation::cur());
+
+                // return an iterable of `q_m*lhs*rhs + q_l*lhs + q_r*rhs + q_o*out + q_c`
+                // each instruction picks `q_*` values that collapse this down
+                // to the specific constraint it needs
+                vec![q_m * lhs.clone() * rhs.clone() + q_l * lhs + q_r * rhs + q_o * out + q_c]
+            },
+        );
 
         // enable instance equality checks
         meta.enable_equality(instance);
+        // enable the fixed column to be used as a source of circuit-wide constants
+        meta.enable_constant(constant);
 
         // return the top-level config
         ArithmeticConfig {
             a,
             b,
+            constant,
             instance,
-            add_config,
-            sub_config,
-            mul_config,
+            q_m,
+            q_l,
+            q_r,
+            q_o,
+            q_c,
         }
     }
+
+    /// Assigns the `q_*` fixed coefficients realizing one row of the
+    /// universal gate; shared by every `ArithmeticInstructions` operator.
+    fn assign_selectors(
+        config: &ArithmeticConfig,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        q_m: F,
+        q_l: F,
+        q_r: F,
+        q_o: F,
+        q_c: F,
+    ) -> Result<(), Error> {
+        region.assign_fixed(|| "q_m", config.q_m, offset, || Value::known(q_m))?;
+        region.assign_fixed(|| "q_l", config.q_l, offset, || Value::known(q_l))?;
+        region.assign_fixed(|| "q_r", config.q_r, offset, || Value::known(q_r))?;
+        region.assign_fixed(|| "q_o", config.q_o, offset, || Value::known(q_o))?;
+        region.assign_fixed(|| "q_c", config.q_c, offset, || Value::known(q_c))?;
+        Ok(())
+    }
 }
 
 /// Halo2 Chip implementation for ArithmeticChip
@@ -156,6 +240,28 @@ impl<F: FieldExt> ArithmeticInstructions<F> for ArithmeticChip<F> {
         )
     }
 
+    /// Loads a fixed constant into the circuit
+    fn load_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        constant: F,
+    ) -> Result<<Self as ArithmeticInstructions<F>>::Num, Error> {
+        // get config
+        let config = self.config();
+
+        // assign region of gates and return
+        layouter.assign_region(
+            // region name
+            || "load constant",
+            // assignment
+            |mut region| {
+                region
+                    .assign_advice_from_constant(|| "constant", config.a, 0, constant)
+                    .map(Number)
+            },
+        )
+    }
+
     /// Expose a number as a public input to the circuit
     fn expose_public(
         &self,
@@ -170,6 +276,21 @@ impl<F: FieldExt> ArithmeticInstructions<F> for ArithmeticChip<F> {
         // publicly exposing the number
         layouter.constrain_instance(num.0.cell(), config.instance, row)
     }
+
+    /// Fused `(a + b) * c` instruction implementation
+    fn add_and_mul(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: <Self as ArithmeticInstructions<F>>::Num,
+        b: <Self as ArithmeticInstructions<F>>::Num,
+        c: <Self as ArithmeticInstructions<F>>::Num,
+    ) -> Result<<Self as ArithmeticInstructions<F>>::Num, Error> {
+        // add `a` and `b` first, using the addition instruction
+        let sum = self.add(layouter, a, b)?;
+
+        // feed the sum as one multiplicand into the multiplication instruction
+        self.mul(layouter, sum, c)
+    }
 }
 
 /// Addition instruction set implementation for ArithmeticChip.
@@ -184,14 +305,42 @@ impl<F: FieldExt> AddInstructions<F> for ArithmeticChip<F> {
         a: Self::Num,
         b: Self::Num,
     ) -> Result<Self::Num, Error> {
-        // configure the add chip
-        let config = self.config().add_config.clone();
-
-        // construct the add chip
-        let add_chip = AddChip::<F>::construct(config, ());
+        // get config
+        let config = self.config().clone();
 
-        // return the result of add_chip's addition gate
-        add_chip.add(layouter, a, b)
+        // assign a region of the shared gate and return
+        layouter.assign_region(
+            // region name
+            || "add",
+            // assignment
+            |mut region: Region<'_, F>| {
+                // realize `lhs + rhs - out == 0` on this row:
+                // q_l = 1, q_r = 1, q_o = -1, q_m = q_c = 0
+                Self::assign_selectors(
+                    &config,
+                    &mut region,
+                    0,
+                    F::zero(),
+                    F::one(),
+                    F::one(),
+                    -F::one(),
+                    F::zero(),
+                )?;
+
+                // copy advice value a to offset zero, column a of the region
+                a.0.copy_advice(|| "lhs", &mut region, config.a, 0)?;
+                // copy advice value b to offset zero, column b of the region
+                b.0.copy_advice(|| "rhs", &mut region, config.b, 0)?;
+
+                // add the values in columns a and b at offset zero
+                let out = a.0.value().copied() + b.0.value();
+
+                // assign the sum as an advice into column a, offset one
+                region
+                    .assign_advice(|| "lhs + rhs", config.a, 1, || out)
+                    .map(Number)
+            },
+        )
     }
 }
 
@@ -200,21 +349,49 @@ impl<F: FieldExt> MulInstructions<F> for ArithmeticChip<F> {
     /// Numeric type definition.
     type Num = Number<F>;
 
-    /// Addition instruction definition.
+    /// Multiplication instruction definition.
     fn mul(
         &self,
         layouter: &mut impl Layouter<F>,
         a: Self::Num,
         b: Self::Num,
     ) -> Result<Self::Num, Error> {
-        // configure the mul chip
-        let config = self.config().mul_config.clone();
-
-        // construct the add chip
-        let mul_chip = MulChip::<F>::construct(config, ());
+        // get config
+        let config = self.config().clone();
 
-        // return the result of add_chip's multiplication gate
-        mul_chip.mul(layouter, a, b)
+        // assign a region of the shared gate and return
+        layouter.assign_region(
+            // region name
+            || "mul",
+            // assignment
+            |mut region: Region<'_, F>| {
+                // realize `lhs * rhs - out == 0` on this row:
+                // q_m = 1, q_o = -1, q_l = q_r = q_c = 0
+                Self::assign_selectors(
+                    &config,
+                    &mut region,
+                    0,
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    -F::one(),
+                    F::zero(),
+                )?;
+
+                // copy advice value a to offset zero, column a of the region
+                a.0.copy_advice(|| "lhs", &mut region, config.a, 0)?;
+                // copy advice value b to offset zero, column b of the region
+                b.0.copy_advice(|| "rhs", &mut region, config.b, 0)?;
+
+                // multiply the values in columns a and b at offset zero
+                let out = a.0.value().copied() * b.0.value();
+
+                // assign the product as an advice into column a, offset one
+                region
+                    .assign_advice(|| "lhs * rhs", config.a, 1, || out)
+                    .map(Number)
+            },
+        )
     }
 }
 
@@ -223,20 +400,112 @@ impl<F: FieldExt> SubInstructions<F> for ArithmeticChip<F> {
     /// Numeric type definition.
     type Num = Number<F>;
 
-    /// Addition instruction definition.
+    /// Subtraction instruction definition.
     fn sub(
         &self,
         layouter: &mut impl Layouter<F>,
         a: Self::Num,
         b: Self::Num,
     ) -> Result<Self::Num, Error> {
-        // configure the sub chip
-        let config = self.config().sub_config.clone();
+        // get config
+        let config = self.config().clone();
+
+        // assign a region of the shared gate and return
+        layouter.assign_region(
+            // region name
+            || "sub",
+            // assignment
+            |mut region: Region<'_, F>| {
+                // realize `lhs - rhs - out == 0` on this row:
+                // q_l = 1, q_r = -1, q_o = -1, q_m = q_c = 0
+                Self::assign_selectors(
+                    &config,
+                    &mut region,
+                    0,
+                    F::zero(),
+                    F::one(),
+                    -F::one(),
+                    -F::one(),
+                    F::zero(),
+                )?;
+
+                // copy advice value a to offset zero, column a of the region
+                a.0.copy_advice(|| "lhs", &mut region, config.a, 0)?;
+                // copy advice value b to offset zero, column b of the region
+                b.0.copy_advice(|| "rhs", &mut region, config.b, 0)?;
+
+                // subtract the values in columns a and b at offset zero
+                let out = a.0.value().copied() - b.0.value();
+
+                // assign the difference as an advice into column a, offset one
+                region
+                    .assign_advice(|| "lhs - rhs", config.a, 1, || out)
+                    .map(Number)
+            },
+        )
+    }
+}
 
-        // construct the add chip
-        let sub_chip = SubChip::<F>::construct(config, ());
+/// Division instruction set implementation for ArithmeticChip.
+impl<F: FieldExt> DivInstructions<F> for ArithmeticChip<F> {
+    /// Numeric type definition.
+    type Num = Number<F>;
+
+    /// Division instruction definition.
+    fn div(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        // get config
+        let config = self.config().clone();
 
-        // return the result of sub_chip's subtraction gate
-        sub_chip.sub(layouter, a, b)
+        // assign a region of the shared gate and return
+        layouter.assign_region(
+            // region name
+            || "div",
+            // assignment
+            |mut region: Region<'_, F>| {
+                // division cannot be expressed as a polynomial directly, so this
+                // row realizes `divisor * quotient - dividend == 0` instead:
+                // q_m = 1, q_o = -1, q_l = q_r = q_c = 0, with the divisor in
+                // column a, the witnessed quotient in column b, and the
+                // dividend copied into the output slot
+                Self::assign_selectors(
+                    &config,
+                    &mut region,
+                    0,
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    -F::one(),
+                    F::zero(),
+                )?;
+
+                // copy the divisor into column a, offset zero
+                b.0.copy_advice(|| "divisor", &mut region, config.a, 0)?;
+
+                // witness the quotient c = a / b = a * b.invert()
+                // `invert()` returns a `CtOption`, which is none when `b == 0`;
+                // `unwrap_or(F::zero())` keeps witness generation from panicking
+                // in that case, letting the gate above report an unsatisfiable
+                // constraint instead when the dividend is non-zero. There is no
+                // separate zero-check on the divisor: for `0 / 0` the row reads
+                // `0 * 0 - 0 == 0` and is satisfied
+                let quotient = a.0.value().zip(b.0.value()).map(|(a, b)| {
+                    let b_inv = b.invert().unwrap_or(F::zero());
+                    *a * b_inv
+                });
+                let quotient_cell =
+                    region.assign_advice(|| "quotient", config.b, 0, || quotient)?;
+
+                // copy the dividend into the output slot, offset one, so the
+                // gate above actually constrains `divisor * quotient == dividend`
+                a.0.copy_advice(|| "dividend", &mut region, config.a, 1)?;
+
+                Ok(Number(quotient_cell))
+            },
+        )
     }
 }
@@ -0,0 +1,20 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::Error,
+};
+
+/// Division intruction set
+pub trait DivInstructions<F: FieldExt>: Chip<F> {
+    /// Numeric variable
+    type Num;
+
+    /// Division instruction
+    /// Takes two inputs and return the quotient
+    fn div(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+}
@@ -0,0 +1,226 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+use crate::chips::arithmetic::{ArithmeticChip, ArithmeticConfig, ArithmeticInstructions, Number};
+
+/// A small arithmetic expression tree
+///
+/// Leaves are private witnesses or constants; internal nodes are the four
+/// operators the calculator supports. `ArithmeticChip::evaluate` lowers a
+/// tree into the matching sequence of `ArithmeticInstructions` calls.
+pub enum Expr<F: FieldExt> {
+    /// A private witnessed value, loaded via `load_private`
+    Private(Value<F>),
+    /// A constant bound into the circuit via the fixed column
+    Constant(F),
+    /// `lhs + rhs`
+    Add(Box<Expr<F>>, Box<Expr<F>>),
+    /// `lhs - rhs`
+    Sub(Box<Expr<F>>, Box<Expr<F>>),
+    /// `lhs * rhs`
+    Mul(Box<Expr<F>>, Box<Expr<F>>),
+    /// `lhs / rhs`
+    Div(Box<Expr<F>>, Box<Expr<F>>),
+}
+
+impl<F: FieldExt> Expr<F> {
+    /// Returns a copy of this tree with every private witness degraded to
+    /// `Value::unknown()`, preserving the tree's shape. Constants are kept
+    /// as-is since they aren't witnesses. This is what lets `ExprCircuit`
+    /// implement `without_witnesses` without special-casing a missing
+    /// expression: the same tree, and so the same lowering path, is used
+    /// for both key generation and proving.
+    fn without_witnesses(&self) -> Self {
+        match self {
+            Expr::Private(_) => Expr::Private(Value::unknown()),
+            Expr::Constant(constant) => Expr::Constant(*constant),
+            Expr::Add(lhs, rhs) => Expr::Add(
+                Box::new(lhs.without_witnesses()),
+                Box::new(rhs.without_witnesses()),
+            ),
+            Expr::Sub(lhs, rhs) => Expr::Sub(
+                Box::new(lhs.without_witnesses()),
+                Box::new(rhs.without_witnesses()),
+            ),
+            Expr::Mul(lhs, rhs) => Expr::Mul(
+                Box::new(lhs.without_witnesses()),
+                Box::new(rhs.without_witnesses()),
+            ),
+            Expr::Div(lhs, rhs) => Expr::Div(
+                Box::new(lhs.without_witnesses()),
+                Box::new(rhs.without_witnesses()),
+            ),
+        }
+    }
+}
+
+/// Arithmetic chip implementation
+impl<F: FieldExt> ArithmeticChip<F> {
+    /// Lowers `expr` into the right chain of `ArithmeticInstructions` calls,
+    /// threading each intermediate `Number<F>` into the next, then exposes
+    /// the root result as a public input at `public_row`
+    pub fn evaluate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        expr: &Expr<F>,
+        public_row: usize,
+    ) -> Result<(), Error> {
+        // recursively lower the expression tree into chip calls
+        let result = self.evaluate_node(&mut layouter, expr)?;
+
+        // expose the root result as a public input
+        self.expose_public(layouter, result, public_row)
+    }
+
+    /// Lowers one node of the expression tree, recursing into its children
+    /// before wiring them through the matching instruction
+    fn evaluate_node(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        expr: &Expr<F>,
+    ) -> Result<Number<F>, Error> {
+        match expr {
+            Expr::Private(value) => self.load_private(layouter.namespace(|| "private"), *value),
+            Expr::Constant(constant) => {
+                self.load_constant(layouter.namespace(|| "constant"), *constant)
+            }
+            Expr::Add(lhs, rhs) => {
+                let lhs = self.evaluate_node(layouter, lhs)?;
+                let rhs = self.evaluate_node(layouter, rhs)?;
+                self.add(layouter, lhs, rhs)
+            }
+            Expr::Sub(lhs, rhs) => {
+                let lhs = self.evaluate_node(layouter, lhs)?;
+                let rhs = self.evaluate_node(layouter, rhs)?;
+                self.sub(layouter, lhs, rhs)
+            }
+            Expr::Mul(lhs, rhs) => {
+                let lhs = self.evaluate_node(layouter, lhs)?;
+                let rhs = self.evaluate_node(layouter, rhs)?;
+                self.mul(layouter, lhs, rhs)
+            }
+            Expr::Div(lhs, rhs) => {
+                let lhs = self.evaluate_node(layouter, lhs)?;
+                let rhs = self.evaluate_node(layouter, rhs)?;
+                self.div(layouter, lhs, rhs)
+            }
+        }
+    }
+}
+
+/// Circuit wrapping an `Expr` tree so it can be run directly against
+/// `MockProver` without hand-wiring chip calls
+pub struct ExprCircuit<F: FieldExt> {
+    /// The expression to evaluate
+    pub expr: Expr<F>,
+    /// Instance row the root result is exposed at
+    pub public_row: usize,
+}
+
+/// Halo2 Circuit implementation for ExprCircuit
+impl<F: FieldExt> Circuit<F> for ExprCircuit<F> {
+    /// Arithmetic configuration
+    type Config = ArithmeticConfig;
+    /// Simple row-by-row floor planner
+    type FloorPlanner = SimpleFloorPlanner;
+
+    /// Returns a copy of this circuit with no witness data, for generating
+    /// the verifying key
+    fn without_witnesses(&self) -> Self {
+        Self {
+            expr: self.expr.without_witnesses(),
+            public_row: self.public_row,
+        }
+    }
+
+    /// Configure ExprCircuit and return the Config
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        // allocate the columns ArithmeticChip needs
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+
+        ArithmeticChip::configure(meta, a, b, constant, instance)
+    }
+
+    /// Synthesize the circuit by evaluating the wrapped expression
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        // construct the chip from its configuration
+        let chip = ArithmeticChip::<F>::construct(config, ());
+
+        chip.evaluate(layouter, &self.expr, self.public_row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    use super::*;
+
+    // small enough to hold every tree below, generous enough to leave room
+    const K: u32 = 4;
+
+    #[test]
+    fn evaluates_add_and_mul_chain() {
+        // (2 + 3) * 4 = 20
+        let expr = Expr::Mul(
+            Box::new(Expr::Add(
+                Box::new(Expr::Private(Value::known(Fp::from(2)))),
+                Box::new(Expr::Private(Value::known(Fp::from(3)))),
+            )),
+            Box::new(Expr::Constant(Fp::from(4))),
+        );
+
+        let circuit = ExprCircuit {
+            expr,
+            public_row: 0,
+        };
+
+        let prover = MockProver::run(K, &circuit, vec![vec![Fp::from(20)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn evaluates_sub_and_div_chain() {
+        // (10 - 4) / 2 = 3
+        let expr = Expr::Div(
+            Box::new(Expr::Sub(
+                Box::new(Expr::Private(Value::known(Fp::from(10)))),
+                Box::new(Expr::Constant(Fp::from(4))),
+            )),
+            Box::new(Expr::Private(Value::known(Fp::from(2)))),
+        );
+
+        let circuit = ExprCircuit {
+            expr,
+            public_row: 0,
+        };
+
+        let prover = MockProver::run(K, &circuit, vec![vec![Fp::from(3)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn division_by_zero_is_an_unsatisfied_constraint_not_a_panic() {
+        // 10 / 0: the quotient is witnessed as zero rather than panicking,
+        // so `divisor * quotient - dividend == 0` becomes `0 - 10 == 0`,
+        // which MockProver must reject regardless of the claimed public input
+        let expr = Expr::Div(
+            Box::new(Expr::Private(Value::known(Fp::from(10)))),
+            Box::new(Expr::Private(Value::known(Fp::zero()))),
+        );
+
+        let circuit = ExprCircuit {
+            expr,
+            public_row: 0,
+        };
+
+        let prover = MockProver::run(K, &circuit, vec![vec![Fp::zero()]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}